@@ -1,10 +1,15 @@
+use base64;
+use bigdecimal;
 use graphql_parser::query;
 use graphql_parser::schema;
 use hex;
 use num_bigint;
-use serde::{self, Deserialize, Serialize};
+use num_traits::ToPrimitive;
+use serde::de::{DeserializeOwned, IntoDeserializer};
+use serde::{self, de, ser, Deserialize, Serialize};
 
 use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
 use std::fmt::{self, Display, Formatter};
 use std::iter::FromIterator;
 use std::ops::{Deref, DerefMut};
@@ -15,6 +20,8 @@ pub type Attribute = String;
 
 pub const BYTES_SCALAR: &str = "Bytes";
 pub const BIG_INT_SCALAR: &str = "BigInt";
+pub const BIG_DECIMAL_SCALAR: &str = "BigDecimal";
+pub const JSON_SCALAR: &str = "JSON";
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct BigInt(num_bigint::BigInt);
@@ -23,6 +30,28 @@ impl BigInt {
     pub fn from_signed_bytes_le(bytes: &[u8]) -> Self {
         BigInt(num_bigint::BigInt::from_signed_bytes_le(bytes))
     }
+
+    /// Returns this `BigInt` as an `i64`, or `None` if it doesn't fit.
+    pub fn to_i64(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
+
+    /// Returns this `BigInt` as a `u64`, or `None` if it doesn't fit.
+    pub fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(i: i64) -> Self {
+        BigInt(num_bigint::BigInt::from(i))
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(u: u64) -> Self {
+        BigInt(num_bigint::BigInt::from(u))
+    }
 }
 
 impl Display for BigInt {
@@ -54,23 +83,118 @@ impl<'de> Deserialize<'de> for BigInt {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BigDecimal(bigdecimal::BigDecimal);
+
+impl Display for BigDecimal {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for BigDecimal {
+    type Err = <bigdecimal::BigDecimal as FromStr>::Err;
+
+    fn from_str(s: &str) -> Result<BigDecimal, Self::Err> {
+        bigdecimal::BigDecimal::from_str(s).map(|x| BigDecimal(x))
+    }
+}
+
+impl Serialize for BigDecimal {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BigDecimal {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let decimal_string = String::deserialize(deserializer)?;
+        BigDecimal::from_str(&decimal_string).map_err(D::Error::custom)
+    }
+}
+
 /// An attribute value is represented as an enum with variants for all supported value types.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum Value {
     String(String),
     Int(i32),
-    Float(f32),
+    Float(f64),
     Bool(bool),
     List(Vec<Value>),
     Null,
     /// In GraphQL, a hex string prefixed by `0x`.
     Bytes(Box<[u8]>),
     BigInt(BigInt),
+    BigDecimal(BigDecimal),
+    /// A nested map of attribute values, used for arbitrary structured data.
+    Object(BTreeMap<String, Value>),
+}
+
+/// An error returned by `Value::from_query_value` when a `query::Value` cannot be converted
+/// into a `Value` of the expected GraphQL type.
+#[derive(Debug, PartialEq)]
+pub enum ValueConversionError {
+    /// The string is not valid hex for a `Bytes` value; carries the source string and the
+    /// underlying decode error.
+    InvalidHex(String, String),
+    /// The string is not a valid `BigInt`; carries the source string and the underlying
+    /// parse error.
+    InvalidBigInt(String, String),
+    /// The string is not a valid `BigDecimal`; carries the source string and the underlying
+    /// parse error.
+    InvalidBigDecimal(String, String),
+    /// The integer literal does not fit into an `i64`.
+    IntegerOutOfRange(query::Number),
+    /// The value's shape does not match the expected GraphQL type.
+    TypeMismatch(query::Value, schema::Type),
+    /// Converting an element of a list failed; the `usize` is the index of the first
+    /// element that failed to convert.
+    ListElement(usize, Box<ValueConversionError>),
+    /// A `query::Value::Variable` was encountered where a concrete value was expected, e.g.
+    /// nested inside a `JSON` scalar, which has no schema of its own to resolve it against.
+    UnsupportedVariable(String),
+}
+
+impl Display for ValueConversionError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ValueConversionError::InvalidHex(s, reason) => {
+                write!(f, "`{}` is not a valid hex string: {}", s, reason)
+            }
+            ValueConversionError::InvalidBigInt(s, reason) => {
+                write!(f, "`{}` is not a valid BigInt: {}", s, reason)
+            }
+            ValueConversionError::InvalidBigDecimal(s, reason) => {
+                write!(f, "`{}` is not a valid BigDecimal: {}", s, reason)
+            }
+            ValueConversionError::IntegerOutOfRange(n) => {
+                write!(f, "integer {:?} does not fit into an i64", n)
+            }
+            ValueConversionError::TypeMismatch(value, ty) => {
+                write!(f, "value {:?} does not match type {:?}", value, ty)
+            }
+            ValueConversionError::ListElement(index, source) => write!(
+                f,
+                "error converting list element at index {}: {}",
+                index, source
+            ),
+            ValueConversionError::UnsupportedVariable(name) => {
+                write!(f, "unexpected variable `${}` in a value with no schema", name)
+            }
+        }
+    }
 }
 
+impl std::error::Error for ValueConversionError {}
+
 impl Value {
-    pub fn from_query_value(value: &query::Value, ty: &schema::Type) -> Value {
+    pub fn from_query_value(
+        value: &query::Value,
+        ty: &schema::Type,
+    ) -> Result<Value, ValueConversionError> {
         use self::schema::Type::{ListType, NamedType};
 
         match (value, ty) {
@@ -78,31 +202,83 @@ impl Value {
                 // Check if `ty` is a custom scalar type, otherwise assume it's
                 // just a string.
                 match n.as_str() {
-                    BYTES_SCALAR => Value::Bytes(
-                        hex::decode(s.trim_left_matches("0x"))
-                            .expect("Value is not a hex string")
-                            .into(),
-                    ),
-                    BIG_INT_SCALAR => {
-                        Value::BigInt(BigInt::from_str(s).expect("Value is not a number"))
+                    BYTES_SCALAR => {
+                        let bytes = hex::decode(s.trim_left_matches("0x")).map_err(|e| {
+                            ValueConversionError::InvalidHex(s.clone(), e.to_string())
+                        })?;
+                        Ok(Value::Bytes(bytes.into()))
                     }
-                    _ => Value::String(s.clone()),
+                    BIG_INT_SCALAR => BigInt::from_str(s)
+                        .map(Value::BigInt)
+                        .map_err(|e| ValueConversionError::InvalidBigInt(s.clone(), e.to_string())),
+                    BIG_DECIMAL_SCALAR => BigDecimal::from_str(s)
+                        .map(Value::BigDecimal)
+                        .map_err(|e| {
+                            ValueConversionError::InvalidBigDecimal(s.clone(), e.to_string())
+                        }),
+                    _ => Ok(Value::String(s.clone())),
                 }
             }
-            (query::Value::Int(i), _) => Value::Int(i.to_owned()
+            (query::Value::Int(i), _) => i
+                .to_owned()
                 .as_i64()
-                .expect("Unable to parse graphql_parser::query::Number into i64")
-                as i32),
-            (query::Value::Float(f), _) => Value::Float(f.to_owned() as f32),
-            (query::Value::Boolean(b), _) => Value::Bool(b.to_owned()),
-            (query::Value::List(values), ListType(ty)) => Value::List(
-                values
-                    .iter()
-                    .map(|value| Self::from_query_value(value, ty))
-                    .collect(),
-            ),
-            (query::Value::Null, _) => Value::Null,
-            _ => unimplemented!(),
+                .map(int_to_value)
+                .ok_or_else(|| ValueConversionError::IntegerOutOfRange(i.to_owned())),
+            (query::Value::Float(f), _) => Ok(Value::Float(f.to_owned())),
+            (query::Value::Boolean(b), _) => Ok(Value::Bool(b.to_owned())),
+            (query::Value::List(values), ListType(ty)) => values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    Self::from_query_value(value, ty).map_err(|e| {
+                        ValueConversionError::ListElement(index, Box::new(e))
+                    })
+                })
+                .collect::<Result<Vec<Value>, ValueConversionError>>()
+                .map(Value::List),
+            (query::Value::Null, _) => Ok(Value::Null),
+            (query::Value::Object(map), NamedType(n)) if n == JSON_SCALAR => map
+                .iter()
+                .map(|(k, v)| Self::from_query_value_untyped(v).map(|v| (k.clone(), v)))
+                .collect::<Result<BTreeMap<String, Value>, ValueConversionError>>()
+                .map(Value::Object),
+            (query::Value::List(values), NamedType(n)) if n == JSON_SCALAR => values
+                .iter()
+                .map(Self::from_query_value_untyped)
+                .collect::<Result<Vec<Value>, ValueConversionError>>()
+                .map(Value::List),
+            (value, ty) => Err(ValueConversionError::TypeMismatch(value.clone(), ty.clone())),
+        }
+    }
+
+    /// Converts a `query::Value` into a `Value` without any schema information, used for
+    /// recursively decoding the contents of a `JSON_SCALAR` value, which has no schema of
+    /// its own.
+    fn from_query_value_untyped(value: &query::Value) -> Result<Value, ValueConversionError> {
+        match value {
+            query::Value::String(s) => Ok(Value::String(s.clone())),
+            query::Value::Int(i) => i
+                .to_owned()
+                .as_i64()
+                .map(int_to_value)
+                .ok_or_else(|| ValueConversionError::IntegerOutOfRange(i.to_owned())),
+            query::Value::Float(f) => Ok(Value::Float(f.to_owned())),
+            query::Value::Boolean(b) => Ok(Value::Bool(*b)),
+            query::Value::Null => Ok(Value::Null),
+            query::Value::Enum(s) => Ok(Value::String(s.clone())),
+            query::Value::List(values) => values
+                .iter()
+                .map(Self::from_query_value_untyped)
+                .collect::<Result<Vec<Value>, ValueConversionError>>()
+                .map(Value::List),
+            query::Value::Object(map) => map
+                .iter()
+                .map(|(k, v)| Self::from_query_value_untyped(v).map(|v| (k.clone(), v)))
+                .collect::<Result<BTreeMap<String, Value>, ValueConversionError>>()
+                .map(Value::Object),
+            query::Value::Variable(name) => {
+                Err(ValueConversionError::UnsupportedVariable(name.clone()))
+            }
         }
     }
 }
@@ -112,7 +288,7 @@ impl From<Value> for query::Value {
         match value {
             Value::String(s) => query::Value::String(s.to_string()),
             Value::Int(i) => query::Value::Int(query::Number::from(i)),
-            Value::Float(f) => query::Value::Float(f.into()),
+            Value::Float(f) => query::Value::Float(f),
             Value::Bool(b) => query::Value::Boolean(b),
             Value::Null => query::Value::Null,
             Value::List(values) => {
@@ -120,6 +296,12 @@ impl From<Value> for query::Value {
             }
             Value::Bytes(bytes) => query::Value::String(format!("0x{}", hex::encode(bytes))),
             Value::BigInt(number) => query::Value::String(number.to_string()),
+            Value::BigDecimal(number) => query::Value::String(number.to_string()),
+            Value::Object(map) => query::Value::Object(
+                map.into_iter()
+                    .map(|(k, v)| (k, v.into()))
+                    .collect(),
+            ),
         }
     }
 }
@@ -142,6 +324,695 @@ impl<'a> From<&'a String> for Value {
     }
 }
 
+/// An error occurring while converting to or from a `Value` via `to_value`/`from_value`.
+#[derive(Debug)]
+pub struct Error(String);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::custom(msg)
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::custom(msg)
+    }
+}
+
+/// Converts a `Serialize` value into a `Value`.
+pub fn to_value<T>(value: T) -> Result<Value, Error>
+where
+    T: Serialize,
+{
+    value.serialize(Serializer)
+}
+
+/// Converts a `Value` into any type implementing `DeserializeOwned`.
+pub fn from_value<T>(value: Value) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(value)
+}
+
+fn int_to_value(n: i64) -> Value {
+    match i32::try_from(n) {
+        Ok(n) => Value::Int(n),
+        Err(_) => Value::BigInt(BigInt::from(n)),
+    }
+}
+
+/// Serializes Rust values directly into a `Value`.
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SerializeVec;
+    type SerializeTuple = SerializeVec;
+    type SerializeTupleStruct = SerializeVec;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+        Ok(Value::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+        Ok(Value::Int(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+        Ok(int_to_value(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+        if v <= i64::MAX as u64 {
+            Ok(int_to_value(v as i64))
+        } else {
+            Ok(Value::BigInt(BigInt::from(v)))
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+        self.serialize_f64(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+        Ok(Value::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, Error> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, Error> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+        Ok(Value::Bytes(v.into()))
+    }
+
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(Value::String(variant.to_owned()))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        let mut map = BTreeMap::new();
+        map.insert(variant.to_owned(), to_value(value)?);
+        Ok(Value::Object(map))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeVec, Error> {
+        Ok(SerializeVec {
+            vec: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeVec, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeVec, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant, Error> {
+        Ok(SerializeTupleVariant {
+            variant,
+            vec: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap {
+            map: BTreeMap::new(),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeMap, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeStructVariant, Error> {
+        Ok(SerializeStructVariant {
+            variant,
+            map: BTreeMap::new(),
+        })
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeVec {
+    vec: Vec<Value>,
+}
+
+#[doc(hidden)]
+pub struct SerializeTupleVariant {
+    variant: &'static str,
+    vec: Vec<Value>,
+}
+
+#[doc(hidden)]
+pub struct SerializeMap {
+    map: BTreeMap<String, Value>,
+    next_key: Option<String>,
+}
+
+#[doc(hidden)]
+pub struct SerializeStructVariant {
+    variant: &'static str,
+    map: BTreeMap<String, Value>,
+}
+
+impl ser::SerializeSeq for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.vec.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::List(self.vec))
+    }
+}
+
+impl ser::SerializeTuple for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.vec.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        let mut map = BTreeMap::new();
+        map.insert(self.variant.to_owned(), Value::List(self.vec));
+        Ok(Value::Object(map))
+    }
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        self.next_key = match to_value(key)? {
+            Value::String(s) => Some(s),
+            other => return Err(Error::custom(format!("map key must be a string, found {:?}", other))),
+        };
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .next_key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.map.insert(key, to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Object(self.map))
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.map.insert(key.to_owned(), to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Object(self.map))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.map.insert(key.to_owned(), to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        let mut map = BTreeMap::new();
+        map.insert(self.variant.to_owned(), Value::Object(self.map));
+        Ok(Value::Object(map))
+    }
+}
+
+impl Value {
+    fn as_i64(&self) -> Result<i64, Error> {
+        match self {
+            Value::Int(n) => Ok(i64::from(*n)),
+            Value::BigInt(n) => n
+                .to_i64()
+                .ok_or_else(|| Error::custom(format!("BigInt {} does not fit into an i64", n))),
+            other => Err(Error::custom(format!("expected an integer, found {:?}", other))),
+        }
+    }
+}
+
+macro_rules! deserialize_value_int {
+    ($method:ident, $ty:ty, $visit:ident) => {
+        fn $method<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            let n = self.as_i64()?;
+            let n = <$ty>::try_from(n)
+                .map_err(|_| Error::custom(format!("integer {} out of range", n)))?;
+            visitor.$visit(n)
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::String(s) => visitor.visit_string(s),
+            Value::Int(i) => visitor.visit_i32(i),
+            Value::Float(f) => visitor.visit_f64(f),
+            Value::Bool(b) => visitor.visit_bool(b),
+            Value::List(values) => visitor.visit_seq(SeqDeserializer(values.into_iter())),
+            Value::Null => visitor.visit_unit(),
+            Value::Bytes(b) => visitor.visit_byte_buf(b.into_vec()),
+            Value::BigInt(n) => visitor.visit_string(n.to_string()),
+            Value::BigDecimal(n) => visitor.visit_string(n.to_string()),
+            Value::Object(map) => visitor.visit_map(MapDeserializer {
+                iter: map.into_iter(),
+                value: None,
+            }),
+        }
+    }
+
+    deserialize_value_int!(deserialize_i8, i8, visit_i8);
+    deserialize_value_int!(deserialize_i16, i16, visit_i16);
+    deserialize_value_int!(deserialize_i32, i32, visit_i32);
+    deserialize_value_int!(deserialize_i64, i64, visit_i64);
+    deserialize_value_int!(deserialize_u8, u8, visit_u8);
+    deserialize_value_int!(deserialize_u16, u16, visit_u16);
+    deserialize_value_int!(deserialize_u32, u32, visit_u32);
+
+    fn deserialize_u64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let n = match &self {
+            Value::Int(n) => u64::try_from(*n)
+                .map_err(|_| Error::custom(format!("integer {} out of range", n)))?,
+            Value::BigInt(n) => n
+                .to_u64()
+                .ok_or_else(|| Error::custom(format!("BigInt {} does not fit into a u64", n)))?,
+            other => return Err(Error::custom(format!("expected an integer, found {:?}", other))),
+        };
+        visitor.visit_u64(n)
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Bool(b) => visitor.visit_bool(b),
+            other => Err(Error::custom(format!("expected a bool, found {:?}", other))),
+        }
+    }
+
+    fn deserialize_f32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Float(f) => visitor.visit_f32(f as f32),
+            other => Err(Error::custom(format!("expected a float, found {:?}", other))),
+        }
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Float(f) => visitor.visit_f64(f),
+            other => Err(Error::custom(format!("expected a float, found {:?}", other))),
+        }
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::String(s) => visitor.visit_string(s),
+            other => Err(Error::custom(format!("expected a string, found {:?}", other))),
+        }
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Bytes(b) => visitor.visit_byte_buf(b.into_vec()),
+            other => Err(Error::custom(format!("expected bytes, found {:?}", other))),
+        }
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            other => visitor.visit_some(other),
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_unit(),
+            other => Err(Error::custom(format!("expected null, found {:?}", other))),
+        }
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::List(values) => visitor.visit_seq(SeqDeserializer(values.into_iter())),
+            other => Err(Error::custom(format!("expected a list, found {:?}", other))),
+        }
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Object(map) => visitor.visit_map(MapDeserializer {
+                iter: map.into_iter(),
+                value: None,
+            }),
+            other => Err(Error::custom(format!("expected an object, found {:?}", other))),
+        }
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Value::String(s) => visitor.visit_enum(s.into_deserializer()),
+            Value::Object(map) => {
+                let mut iter = map.into_iter();
+                let (variant, value) = iter
+                    .next()
+                    .ok_or_else(|| Error::custom("expected exactly one key in enum object"))?;
+                if iter.next().is_some() {
+                    return Err(Error::custom("expected exactly one key in enum object"));
+                }
+                visitor.visit_enum(EnumDeserializer { variant, value })
+            }
+            other => Err(Error::custom(format!(
+                "expected a string or object for an enum, found {:?}",
+                other
+            ))),
+        }
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_any(visitor)
+    }
+}
+
+struct SeqDeserializer(std::vec::IntoIter<Value>);
+
+impl<'de> de::SeqAccess<'de> for SeqDeserializer {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        match self.0.next() {
+            Some(value) => seed.deserialize(value).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct MapDeserializer {
+    iter: std::collections::btree_map::IntoIter<String, Value>,
+    value: Option<Value>,
+}
+
+impl<'de> de::MapAccess<'de> for MapDeserializer {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(key.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(value)
+    }
+}
+
+struct EnumDeserializer {
+    variant: String,
+    value: Value,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = Error;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantDeserializer), Error> {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, VariantDeserializer { value: self.value }))
+    }
+}
+
+struct VariantDeserializer {
+    value: Value,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.value {
+            Value::Null => Ok(()),
+            other => Err(Error::custom(format!("expected unit, found {:?}", other))),
+        }
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self.value)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self.value, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self.value, visitor)
+    }
+}
+
 /// An entity is represented as a map of attribute names to values.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Entity(HashMap<Attribute, Value>);
@@ -204,11 +1075,324 @@ impl<'a> From<Vec<(&'a str, Value)>> for Entity {
     }
 }
 
+/// An error that can occur while decoding a pagination `Cursor`.
+#[derive(Debug, PartialEq)]
+pub enum CursorError {
+    /// The cursor is not valid base64, or does not decode into a value this version of
+    /// the cursor format understands.
+    Invalid,
+    /// The cursor decoded fine, but no entity in the slice being paginated matches it, e.g.
+    /// because the entity it pointed at was deleted or reordered since the cursor was handed
+    /// out.
+    NotFound,
+}
+
+impl Display for CursorError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            CursorError::Invalid => write!(f, "invalid pagination cursor"),
+            CursorError::NotFound => {
+                write!(f, "pagination cursor does not match any entity in range")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CursorError {}
+
+/// An opaque, base64-encoded cursor wrapping an entity's ordering key, used to implement
+/// Relay-style pagination.
+pub struct Cursor;
+
+impl Cursor {
+    /// Encodes a `Value` (typically an entity's id or other ordering key) into an opaque
+    /// cursor.
+    pub fn encode(value: &Value) -> String {
+        let mut encoded = String::new();
+        Self::write_field(&mut encoded, value);
+        base64::encode(&encoded)
+    }
+
+    /// Decodes a cursor produced by `encode` back into the `Value` it wraps.
+    pub fn decode(cursor: &str) -> Result<Value, CursorError> {
+        let bytes = base64::decode(cursor).map_err(|_| CursorError::Invalid)?;
+        let decoded = String::from_utf8(bytes).map_err(|_| CursorError::Invalid)?;
+        let (value, remainder) = Self::read_field(&decoded).ok_or(CursorError::Invalid)?;
+        if !remainder.is_empty() {
+            return Err(CursorError::Invalid);
+        }
+        Ok(value)
+    }
+
+    fn write_tagged(buf: &mut String, tag: char, payload: &str) {
+        buf.push(tag);
+        buf.push_str(&payload.len().to_string());
+        buf.push(':');
+        buf.push_str(payload);
+    }
+
+    fn write_field(buf: &mut String, value: &Value) {
+        match value {
+            Value::Null => Self::write_tagged(buf, 'n', ""),
+            Value::Bool(b) => Self::write_tagged(buf, 'b', if *b { "1" } else { "0" }),
+            Value::Int(i) => Self::write_tagged(buf, 'i', &i.to_string()),
+            Value::Float(f) => Self::write_tagged(buf, 'f', &f.to_string()),
+            Value::String(s) => Self::write_tagged(buf, 's', s),
+            Value::Bytes(bytes) => Self::write_tagged(buf, 'y', &hex::encode(bytes)),
+            Value::BigInt(n) => Self::write_tagged(buf, 'g', &n.to_string()),
+            Value::BigDecimal(n) => Self::write_tagged(buf, 'd', &n.to_string()),
+            Value::List(values) => {
+                let mut inner = String::new();
+                for v in values {
+                    Self::write_field(&mut inner, v);
+                }
+                Self::write_tagged(buf, 'l', &inner);
+            }
+            Value::Object(map) => {
+                let mut inner = String::new();
+                for (k, v) in map {
+                    Self::write_tagged(&mut inner, 'k', k);
+                    Self::write_field(&mut inner, v);
+                }
+                Self::write_tagged(buf, 'o', &inner);
+            }
+        }
+    }
+
+    fn read_tagged(input: &str) -> Option<(char, &str, &str)> {
+        let tag = input.chars().next()?;
+        let rest = &input[tag.len_utf8()..];
+        let colon = rest.find(':')?;
+        let len: usize = rest[..colon].parse().ok()?;
+        let payload_start = colon + 1;
+        let payload_end = payload_start.checked_add(len)?;
+        if payload_end > rest.len()
+            || !rest.is_char_boundary(payload_start)
+            || !rest.is_char_boundary(payload_end)
+        {
+            return None;
+        }
+        Some((tag, &rest[payload_start..payload_end], &rest[payload_end..]))
+    }
+
+    fn read_field(input: &str) -> Option<(Value, &str)> {
+        let (tag, payload, remainder) = Self::read_tagged(input)?;
+        let value = match tag {
+            'n' => Value::Null,
+            'b' => Value::Bool(payload == "1"),
+            'i' => Value::Int(payload.parse().ok()?),
+            'f' => Value::Float(payload.parse().ok()?),
+            's' => Value::String(payload.to_owned()),
+            'y' => Value::Bytes(hex::decode(payload).ok()?.into()),
+            'g' => Value::BigInt(BigInt::from_str(payload).ok()?),
+            'd' => Value::BigDecimal(BigDecimal::from_str(payload).ok()?),
+            'l' => {
+                let mut values = Vec::new();
+                let mut rest = payload;
+                while !rest.is_empty() {
+                    let (v, r) = Self::read_field(rest)?;
+                    values.push(v);
+                    rest = r;
+                }
+                Value::List(values)
+            }
+            'o' => {
+                let mut map = BTreeMap::new();
+                let mut rest = payload;
+                while !rest.is_empty() {
+                    let (key_tag, key, r) = Self::read_tagged(rest)?;
+                    if key_tag != 'k' {
+                        return None;
+                    }
+                    let (v, r2) = Self::read_field(r)?;
+                    map.insert(key.to_owned(), v);
+                    rest = r2;
+                }
+                Value::Object(map)
+            }
+            _ => return None,
+        };
+        Some((value, remainder))
+    }
+}
+
+/// One entity in a `Connection`, paired with the opaque cursor pointing at it.
+pub struct Edge {
+    pub cursor: String,
+    pub node: Entity,
+}
+
+impl Into<query::Value> for Edge {
+    fn into(self) -> query::Value {
+        let mut fields = BTreeMap::new();
+        fields.insert("cursor".to_owned(), query::Value::String(self.cursor));
+        fields.insert("node".to_owned(), self.node.into());
+        query::Value::Object(fields)
+    }
+}
+
+/// Pagination state for a `Connection`, following the Relay Cursor Connections spec.
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub start_cursor: Option<String>,
+    pub end_cursor: Option<String>,
+}
+
+impl Into<query::Value> for PageInfo {
+    fn into(self) -> query::Value {
+        let mut fields = BTreeMap::new();
+        fields.insert(
+            "hasNextPage".to_owned(),
+            query::Value::Boolean(self.has_next_page),
+        );
+        fields.insert(
+            "hasPreviousPage".to_owned(),
+            query::Value::Boolean(self.has_previous_page),
+        );
+        fields.insert(
+            "startCursor".to_owned(),
+            self.start_cursor
+                .map(query::Value::String)
+                .unwrap_or(query::Value::Null),
+        );
+        fields.insert(
+            "endCursor".to_owned(),
+            self.end_cursor
+                .map(query::Value::String)
+                .unwrap_or(query::Value::Null),
+        );
+        query::Value::Object(fields)
+    }
+}
+
+/// A Relay Cursor Connections page of entities, as described at
+/// https://relay.dev/graphql/connections.htm.
+pub struct Connection {
+    pub edges: Vec<Edge>,
+    pub page_info: PageInfo,
+}
+
+impl Into<query::Value> for Connection {
+    fn into(self) -> query::Value {
+        let mut fields = BTreeMap::new();
+        fields.insert(
+            "edges".to_owned(),
+            query::Value::List(self.edges.into_iter().map(Into::into).collect()),
+        );
+        fields.insert("pageInfo".to_owned(), self.page_info.into());
+        query::Value::Object(fields)
+    }
+}
+
+/// Builds a `Connection` page from a slice of entities and Relay pagination arguments.
+///
+/// `id_attribute` names the attribute used as each entity's ordering key; `before`/`after`
+/// are cursors previously produced by `Cursor::encode` for that same attribute. `after`/
+/// `before` trim the range first, then `first` keeps the leading entities from what's left
+/// (flagging `has_next_page` if any were dropped) and `last` keeps the trailing entities
+/// (flagging `has_previous_page` if any were dropped).
+pub fn connection_from_entities(
+    entities: &[Entity],
+    id_attribute: &str,
+    first: Option<u32>,
+    last: Option<u32>,
+    before: Option<&str>,
+    after: Option<&str>,
+) -> Result<Connection, CursorError> {
+    let mut start = 0;
+    let mut end = entities.len();
+
+    let mut has_next_page = false;
+    let mut has_previous_page = false;
+
+    if let Some(after) = after {
+        let after_value = Cursor::decode(after)?;
+        let pos = entities
+            .iter()
+            .position(|entity| entity.get(id_attribute) == Some(&after_value))
+            .ok_or(CursorError::NotFound)?;
+        start = pos + 1;
+        has_previous_page = pos > 0;
+    }
+
+    if let Some(before) = before {
+        let before_value = Cursor::decode(before)?;
+        let pos = entities[start..end]
+            .iter()
+            .position(|entity| entity.get(id_attribute) == Some(&before_value))
+            .ok_or(CursorError::NotFound)?;
+        end = start + pos;
+        has_next_page = end < entities.len();
+    }
+
+    if let Some(first) = first {
+        let first = first as usize;
+        if end - start > first {
+            end = start + first;
+            has_next_page = true;
+        }
+    }
+
+    if let Some(last) = last {
+        let last = last as usize;
+        if end - start > last {
+            start = end - last;
+            has_previous_page = true;
+        }
+    }
+
+    let edges: Vec<Edge> = entities[start..end]
+        .iter()
+        .filter_map(|entity| {
+            entity.get(id_attribute).map(|id| Edge {
+                cursor: Cursor::encode(id),
+                node: entity.clone(),
+            })
+        })
+        .collect();
+
+    let start_cursor = edges.first().map(|edge| edge.cursor.clone());
+    let end_cursor = edges.last().map(|edge| edge.cursor.clone());
+
+    Ok(Connection {
+        edges,
+        page_info: PageInfo {
+            has_next_page,
+            has_previous_page,
+            start_cursor,
+            end_cursor,
+        },
+    })
+}
+
+#[test]
+fn value_json() {
+    let mut inner = BTreeMap::new();
+    inner.insert("a".to_owned(), query::Value::Int(query::Number::from(1)));
+    inner.insert(
+        "b".to_owned(),
+        query::Value::List(vec![query::Value::String("x".to_owned())]),
+    );
+    let graphql_value = query::Value::Object(inner);
+    let ty = query::Type::NamedType(JSON_SCALAR.to_owned());
+    let from_query = Value::from_query_value(&graphql_value, &ty).unwrap();
+
+    let mut expected = BTreeMap::new();
+    expected.insert("a".to_owned(), Value::Int(1));
+    expected.insert(
+        "b".to_owned(),
+        Value::List(vec![Value::String("x".to_owned())]),
+    );
+    assert_eq!(from_query, Value::Object(expected));
+    assert_eq!(query::Value::from(from_query), graphql_value);
+}
+
 #[test]
 fn value_bytes() {
     let graphql_value = query::Value::String("0x8f494c66afc1d3f8ac1b45df21f02a46".to_owned());
     let ty = query::Type::NamedType(BYTES_SCALAR.to_owned());
-    let from_query = Value::from_query_value(&graphql_value, &ty);
+    let from_query = Value::from_query_value(&graphql_value, &ty).unwrap();
     assert_eq!(
         from_query,
         Value::Bytes(Box::new([
@@ -218,15 +1402,225 @@ fn value_bytes() {
     assert_eq!(query::Value::from(from_query), graphql_value);
 }
 
+#[test]
+fn value_bytes_invalid_hex() {
+    let graphql_value = query::Value::String("not hex".to_owned());
+    let ty = query::Type::NamedType(BYTES_SCALAR.to_owned());
+    match Value::from_query_value(&graphql_value, &ty) {
+        Err(ValueConversionError::InvalidHex(s, _)) => assert_eq!(s, "not hex"),
+        other => panic!("expected InvalidHex error, got {:?}", other),
+    }
+}
+
+#[test]
+fn value_list_element_error_carries_index() {
+    let graphql_value = query::Value::List(vec![
+        query::Value::String("0x00".to_owned()),
+        query::Value::String("not hex".to_owned()),
+    ]);
+    let ty = query::Type::ListType(Box::new(query::Type::NamedType(BYTES_SCALAR.to_owned())));
+    match Value::from_query_value(&graphql_value, &ty) {
+        Err(ValueConversionError::ListElement(index, _)) => assert_eq!(index, 1),
+        other => panic!("expected ListElement error, got {:?}", other),
+    }
+}
+
+#[test]
+fn value_json_rejects_nested_variable() {
+    let mut inner = BTreeMap::new();
+    inner.insert("x".to_owned(), query::Value::Variable("foo".to_owned()));
+    let graphql_value = query::Value::Object(inner);
+    let ty = query::Type::NamedType(JSON_SCALAR.to_owned());
+    match Value::from_query_value(&graphql_value, &ty) {
+        Err(ValueConversionError::UnsupportedVariable(name)) => assert_eq!(name, "foo"),
+        other => panic!("expected UnsupportedVariable error, got {:?}", other),
+    }
+}
+
+#[test]
+fn value_int_overflowing_i32_promotes_to_bigint() {
+    let document = graphql_parser::parse_query("{ field(x: 9999999999) }").unwrap();
+    let graphql_value = match &document.definitions[0] {
+        query::Definition::Operation(query::OperationDefinition::SelectionSet(set)) => {
+            match &set.items[0] {
+                query::Selection::Field(field) => field.arguments[0].1.clone(),
+                other => panic!("expected a field selection, got {:?}", other),
+            }
+        }
+        other => panic!("expected an anonymous selection set, got {:?}", other),
+    };
+    let ty = query::Type::NamedType("Int".to_owned());
+    assert_eq!(
+        Value::from_query_value(&graphql_value, &ty).unwrap(),
+        Value::BigInt(BigInt::from(9999999999i64))
+    );
+}
+
 #[test]
 fn value_bigint() {
     let big_num = "340282366920938463463374607431768211456";
     let graphql_value = query::Value::String(big_num.to_owned());
     let ty = query::Type::NamedType(BIG_INT_SCALAR.to_owned());
-    let from_query = Value::from_query_value(&graphql_value, &ty);
+    let from_query = Value::from_query_value(&graphql_value, &ty).unwrap();
     assert_eq!(
         from_query,
         Value::BigInt(BigInt::from_str(big_num).unwrap())
     );
     assert_eq!(query::Value::from(from_query), graphql_value);
 }
+
+#[test]
+fn value_bigdecimal() {
+    let big_decimal = "12345678901234567890.1234567890";
+    let graphql_value = query::Value::String(big_decimal.to_owned());
+    let ty = query::Type::NamedType(BIG_DECIMAL_SCALAR.to_owned());
+    let from_query = Value::from_query_value(&graphql_value, &ty).unwrap();
+    assert_eq!(
+        from_query,
+        Value::BigDecimal(BigDecimal::from_str(big_decimal).unwrap())
+    );
+    assert_eq!(query::Value::from(from_query), graphql_value);
+}
+
+#[test]
+fn value_serde_roundtrip_struct() {
+    #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    struct Thing {
+        name: String,
+        count: i32,
+        tags: Vec<String>,
+    }
+
+    let thing = Thing {
+        name: "foo".to_owned(),
+        count: 3,
+        tags: vec!["a".to_owned(), "b".to_owned()],
+    };
+    let value = to_value(&thing).unwrap();
+    assert_eq!(from_value::<Thing>(value).unwrap(), thing);
+}
+
+#[test]
+fn value_serde_promotes_overflowing_integer_to_bigint() {
+    let value = to_value(i64::MAX).unwrap();
+    assert_eq!(value, Value::BigInt(BigInt::from(i64::MAX)));
+    assert_eq!(from_value::<i64>(value).unwrap(), i64::MAX);
+}
+
+#[test]
+fn value_serde_roundtrips_u64_max_through_bigint() {
+    let value = to_value(u64::MAX).unwrap();
+    assert_eq!(value, Value::BigInt(BigInt::from(u64::MAX)));
+    assert_eq!(from_value::<u64>(value).unwrap(), u64::MAX);
+}
+
+#[test]
+fn cursor_roundtrip() {
+    for value in vec![
+        Value::Null,
+        Value::Bool(true),
+        Value::Int(42),
+        Value::String("foo".to_owned()),
+        Value::Bytes(Box::new([1, 2, 3])),
+        Value::BigInt(BigInt::from(12345i64)),
+        Value::List(vec![Value::Int(1), Value::String("x".to_owned())]),
+    ] {
+        let cursor = Cursor::encode(&value);
+        assert_eq!(Cursor::decode(&cursor).unwrap(), value);
+    }
+}
+
+#[test]
+fn cursor_decode_rejects_garbage() {
+    assert_eq!(Cursor::decode("not a cursor"), Err(CursorError::Invalid));
+}
+
+#[test]
+fn cursor_decode_rejects_non_char_boundary_length() {
+    // base64 of `s1:éx`: the declared payload length of 1 byte lands in the middle of
+    // the 2-byte UTF-8 encoding of `é`, which must be rejected instead of panicking.
+    assert_eq!(Cursor::decode("czE6w6l4"), Err(CursorError::Invalid));
+}
+
+#[cfg(test)]
+fn entities_by_id(ids: &[&str]) -> Vec<Entity> {
+    ids.iter()
+        .map(|id| Entity::from(vec![("id", Value::from(*id))]))
+        .collect()
+}
+
+#[test]
+fn connection_from_entities_paginates_with_first() {
+    let entities = entities_by_id(&["a", "b", "c", "d"]);
+    let connection = connection_from_entities(&entities, "id", Some(2), None, None, None).unwrap();
+
+    let ids: Vec<&Value> = connection
+        .edges
+        .iter()
+        .map(|edge| edge.node.get("id").unwrap())
+        .collect();
+    assert_eq!(ids, vec![&Value::from("a"), &Value::from("b")]);
+    assert!(connection.page_info.has_next_page);
+    assert!(!connection.page_info.has_previous_page);
+}
+
+#[test]
+fn connection_from_entities_paginates_with_last() {
+    let entities = entities_by_id(&["a", "b", "c", "d"]);
+    let connection = connection_from_entities(&entities, "id", None, Some(2), None, None).unwrap();
+
+    let ids: Vec<&Value> = connection
+        .edges
+        .iter()
+        .map(|edge| edge.node.get("id").unwrap())
+        .collect();
+    assert_eq!(ids, vec![&Value::from("c"), &Value::from("d")]);
+    assert!(!connection.page_info.has_next_page);
+    assert!(connection.page_info.has_previous_page);
+}
+
+#[test]
+fn connection_from_entities_paginates_after_cursor() {
+    let entities = entities_by_id(&["a", "b", "c", "d"]);
+    let after = Cursor::encode(&Value::from("b"));
+    let connection =
+        connection_from_entities(&entities, "id", None, None, None, Some(&after)).unwrap();
+
+    let ids: Vec<&Value> = connection
+        .edges
+        .iter()
+        .map(|edge| edge.node.get("id").unwrap())
+        .collect();
+    assert_eq!(ids, vec![&Value::from("c"), &Value::from("d")]);
+    assert!(!connection.page_info.has_next_page);
+    assert!(connection.page_info.has_previous_page);
+}
+
+#[test]
+fn connection_from_entities_paginates_before_cursor() {
+    let entities = entities_by_id(&["a", "b", "c", "d"]);
+    let before = Cursor::encode(&Value::from("c"));
+    let connection =
+        connection_from_entities(&entities, "id", None, None, Some(&before), None).unwrap();
+
+    let ids: Vec<&Value> = connection
+        .edges
+        .iter()
+        .map(|edge| edge.node.get("id").unwrap())
+        .collect();
+    assert_eq!(ids, vec![&Value::from("a"), &Value::from("b")]);
+    assert!(connection.page_info.has_next_page);
+    assert!(!connection.page_info.has_previous_page);
+}
+
+#[test]
+fn connection_from_entities_rejects_cursor_not_in_range() {
+    let entities = entities_by_id(&["a", "b", "d"]);
+    // Well-formed cursor pointing at an entity that has since been deleted/reordered out of
+    // this slice (e.g. by a chain reorg), so it no longer matches anything in `entities`.
+    let after = Cursor::encode(&Value::from("c"));
+    match connection_from_entities(&entities, "id", None, None, None, Some(&after)) {
+        Err(CursorError::NotFound) => {}
+        other => panic!("expected CursorError::NotFound, got {:?}", other.map(|_| ())),
+    }
+}